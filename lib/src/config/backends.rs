@@ -3,11 +3,18 @@ mod client_cert_info;
 use async_trait::async_trait;
 use http::{Request, Response};
 use hyper::Body;
+use rustls::{Certificate, ProtocolVersion};
 use {
     hyper::{header::HeaderValue, Uri},
-    std::{collections::HashMap, sync::Arc},
+    std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+        time::Duration,
+    },
 };
 
+use crate::error::DynamicBackendError;
+
 pub use self::client_cert_info::{ClientCertError, ClientCertInfo};
 
 /// A single backend definition.
@@ -20,10 +27,38 @@ pub struct Backend {
     pub grpc: bool,
     pub client_cert: Option<ClientCertInfo>,
 
+    /// The maximum time to wait for a TCP/TLS connection to the backend to be established.
+    pub connect_timeout: Option<Duration>,
+    /// The maximum time to wait for the first byte of the response after the request has
+    /// been sent.
+    pub first_byte_timeout: Option<Duration>,
+    /// The maximum time to wait for each subsequent chunk of the response body, reset after
+    /// every chunk received.
+    pub between_bytes_timeout: Option<Duration>,
+
+    /// A custom CA certificate chain to trust for this backend, in place of the process-wide
+    /// default roots.
+    pub ca_certificate: Option<Vec<Certificate>>,
+    /// The minimum TLS protocol version to allow when connecting to this backend.
+    pub ssl_min_version: Option<ProtocolVersion>,
+    /// The maximum TLS protocol version to allow when connecting to this backend.
+    pub ssl_max_version: Option<ProtocolVersion>,
+
     /// Handler that will be called instead of making an HTTP call.
     pub handler: Option<Handler>,
 }
 
+/// `rustls::ProtocolVersion` only derives `PartialEq`, not `PartialOrd`, so `ssl_min_version`
+/// and `ssl_max_version` can't be compared directly. This ranks the handful of versions we
+/// actually accept (see `parse_ssl_version`) so they can be.
+pub(crate) fn ssl_version_rank(version: ProtocolVersion) -> u8 {
+    match version {
+        ProtocolVersion::TLSv1_2 => 0,
+        ProtocolVersion::TLSv1_3 => 1,
+        _ => u8::MAX,
+    }
+}
+
 #[derive(Clone)]
 pub struct Handler {
     handler: Arc<Box<dyn InMemoryBackendHandler>>,
@@ -64,8 +99,91 @@ pub trait DynamicBackendRegistrar: Send + Sync + 'static {
 }
 
 /// A map of [`Backend`] definitions, keyed by their name.
+///
+/// Backends come from two sources: the `[backends]` table of `fastly.toml`, fixed for the
+/// lifetime of the process, and backends registered at request time via the guest-facing
+/// `register_dynamic_backend` hostcall. Lookups consult both.
 #[derive(Clone, Debug, Default)]
-pub struct BackendsConfig(pub HashMap<String, Arc<Backend>>);
+pub struct BackendsConfig {
+    static_backends: HashMap<String, Arc<Backend>>,
+    dynamic_backends: Arc<RwLock<HashMap<String, Arc<Backend>>>>,
+}
+
+impl BackendsConfig {
+    /// Look up a backend by name, checking the statically configured backends first and then
+    /// any backends registered dynamically in this session.
+    pub fn get(&self, name: &str) -> Option<Arc<Backend>> {
+        self.static_backends
+            .get(name)
+            .cloned()
+            .or_else(|| self.dynamic_backends.read().unwrap().get(name).cloned())
+    }
+
+    /// Register a new backend at request time, as with the `register_dynamic_backend` hostcall.
+    ///
+    /// The name must not already be in use by either a static or a previously registered
+    /// dynamic backend. If a [`DynamicBackendRegistrar`] was configured, it is given a chance
+    /// to transform the backend before it is inserted.
+    pub fn register_dynamic(
+        &self,
+        name: String,
+        backend: Backend,
+        registrar: Option<&dyn DynamicBackendRegistrar>,
+    ) -> Result<(), DynamicBackendError> {
+        if self.static_backends.contains_key(&name) {
+            return Err(DynamicBackendError::NameAlreadyInUse(name));
+        }
+
+        let mut dynamic_backends = self.dynamic_backends.write().unwrap();
+        if dynamic_backends.contains_key(&name) {
+            return Err(DynamicBackendError::NameAlreadyInUse(name));
+        }
+
+        let backend = match registrar {
+            Some(registrar) => registrar.register(backend),
+            None => backend,
+        };
+
+        dynamic_backends.insert(name, Arc::new(backend));
+        Ok(())
+    }
+
+    /// Serialize the statically configured backends back into a TOML table shaped like a
+    /// `fastly.toml`'s `[backends]` section, for [`backends_watcher`][super::backends_watcher]'s
+    /// save path.
+    ///
+    /// Dynamically registered backends are not included: they never came from `fastly.toml` and
+    /// don't belong in it.
+    pub fn to_toml_table(&self) -> toml::value::Table {
+        self.static_backends
+            .iter()
+            .filter_map(|(name, backend)| {
+                toml::Value::try_from(backend.as_ref())
+                    .ok()
+                    .map(|value| (name.clone(), value))
+            })
+            .collect()
+    }
+
+    /// Replace the statically configured backends in place, e.g. after reloading a `fastly.toml`
+    /// that was edited on disk.
+    ///
+    /// Unlike swapping in a whole new [`BackendsConfig`], this leaves `dynamic_backends` alone,
+    /// so backends registered at request time via `register_dynamic` stay resolvable across a
+    /// reload of unrelated static configuration.
+    pub fn replace_static(&mut self, static_backends: HashMap<String, Arc<Backend>>) {
+        self.static_backends = static_backends;
+    }
+}
+
+impl From<HashMap<String, Arc<Backend>>> for BackendsConfig {
+    fn from(static_backends: HashMap<String, Arc<Backend>>) -> Self {
+        Self {
+            static_backends,
+            dynamic_backends: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
 
 /// This module contains [`TryFrom`] implementations used when deserializing a `fastly.toml`.
 ///
@@ -74,13 +192,105 @@ pub struct BackendsConfig(pub HashMap<String, Arc<Backend>>);
 /// not valid, a [`FastlyConfigError`] will be returned.
 mod deserialization {
     use {
-        super::{Backend, BackendsConfig},
+        super::{client_cert_info::ClientCertInfo, ssl_version_rank, Backend, BackendsConfig},
         crate::error::{BackendConfigError, FastlyConfigError},
         hyper::{header::HeaderValue, Uri},
-        std::sync::Arc,
+        rustls::{Certificate, ProtocolVersion},
+        std::{collections::HashMap, fs, sync::Arc, time::Duration},
         toml::value::{Table, Value},
     };
 
+    /// Parse an optional timeout entry, given as an integer number of milliseconds, into a
+    /// [`Duration`].
+    fn parse_timeout_ms(
+        toml: &mut Table,
+        key: &'static str,
+    ) -> Result<Option<Duration>, BackendConfigError> {
+        toml.remove(key)
+            .map(|value| match value.as_integer() {
+                Some(ms) if ms >= 0 => Ok(Duration::from_millis(ms as u64)),
+                _ => Err(BackendConfigError::InvalidTimeoutEntry { key }),
+            })
+            .transpose()
+    }
+
+    /// Read a PEM value, either given inline or, if prefixed with `@`, loaded from the path that
+    /// follows the `@`.
+    fn read_pem_entry(value: &str) -> Result<Vec<u8>, BackendConfigError> {
+        match value.strip_prefix('@') {
+            Some(path) => fs::read(path).map_err(BackendConfigError::InvalidClientCertPath),
+            None => Ok(value.as_bytes().to_vec()),
+        }
+    }
+
+    /// Parse the `client_certificate`/`client_key` pair, if present, into a [`ClientCertInfo`].
+    ///
+    /// Both keys must be present together, since a certificate chain without a matching private
+    /// key (or vice versa) cannot be used to authenticate.
+    fn parse_client_cert(toml: &mut Table) -> Result<Option<ClientCertInfo>, BackendConfigError> {
+        let client_certificate = toml.remove("client_certificate");
+        let client_key = toml.remove("client_key");
+
+        match (client_certificate, client_key) {
+            (None, None) => Ok(None),
+            (Some(_), None) | (None, Some(_)) => Err(BackendConfigError::IncompleteClientCert),
+            (Some(cert), Some(key)) => {
+                let cert = match cert {
+                    Value::String(cert) => cert,
+                    _ => return Err(BackendConfigError::InvalidClientCertificateEntry),
+                };
+                let key = match key {
+                    Value::String(key) => key,
+                    _ => return Err(BackendConfigError::InvalidClientKeyEntry),
+                };
+
+                let cert_pem = read_pem_entry(&cert)?;
+                let key_pem = read_pem_entry(&key)?;
+
+                Ok(Some(ClientCertInfo::parse(&cert_pem, &key_pem)?))
+            }
+        }
+    }
+
+    /// Parse the `ca_certificate` entry, if present, into a chain of [`Certificate`]s to trust
+    /// for this backend, in place of the process-wide default roots.
+    fn parse_ca_certificate(
+        toml: &mut Table,
+    ) -> Result<Option<Vec<Certificate>>, BackendConfigError> {
+        toml.remove("ca_certificate")
+            .map(|ca_certificate| match ca_certificate {
+                Value::String(ca_certificate) => {
+                    let pem = read_pem_entry(&ca_certificate)?;
+                    let certs = rustls_pemfile::certs(&mut &pem[..])
+                        .map_err(|_| BackendConfigError::InvalidCaCertificatePem)?
+                        .into_iter()
+                        .map(Certificate)
+                        .collect::<Vec<_>>();
+                    if certs.is_empty() {
+                        Err(BackendConfigError::InvalidCaCertificatePem)
+                    } else {
+                        Ok(certs)
+                    }
+                }
+                _ => Err(BackendConfigError::InvalidCaCertificateEntry),
+            })
+            .transpose()
+    }
+
+    /// Parse a TLS protocol version string (`"1.2"` or `"1.3"`) into a [`ProtocolVersion`].
+    fn parse_ssl_version(
+        toml: &mut Table,
+        key: &'static str,
+    ) -> Result<Option<ProtocolVersion>, BackendConfigError> {
+        toml.remove(key)
+            .map(|value| match value {
+                Value::String(ref v) if v == "1.2" => Ok(ProtocolVersion::TLSv1_2),
+                Value::String(ref v) if v == "1.3" => Ok(ProtocolVersion::TLSv1_3),
+                _ => Err(BackendConfigError::InvalidSslVersionEntry { key }),
+            })
+            .transpose()
+    }
+
     /// Helper function for converting a TOML [`Value`] into a [`Table`].
     ///
     /// This function checks that a value is a [`Value::Table`] variant and returns the underlying
@@ -124,8 +334,8 @@ mod deserialization {
 
             toml.into_iter()
                 .map(process_entry)
-                .collect::<Result<_, _>>()
-                .map(Self)
+                .collect::<Result<HashMap<_, _>, _>>()
+                .map(Self::from)
         }
     }
 
@@ -184,6 +394,21 @@ mod deserialization {
                 .transpose()?
                 .unwrap_or(false);
 
+            let connect_timeout = parse_timeout_ms(&mut toml, "connect_timeout")?;
+            let first_byte_timeout = parse_timeout_ms(&mut toml, "first_byte_timeout")?;
+            let between_bytes_timeout = parse_timeout_ms(&mut toml, "between_bytes_timeout")?;
+
+            let client_cert = parse_client_cert(&mut toml)?;
+
+            let ca_certificate = parse_ca_certificate(&mut toml)?;
+            let ssl_min_version = parse_ssl_version(&mut toml, "ssl_min_version")?;
+            let ssl_max_version = parse_ssl_version(&mut toml, "ssl_max_version")?;
+            if let (Some(min), Some(max)) = (ssl_min_version, ssl_max_version) {
+                if ssl_version_rank(min) > ssl_version_rank(max) {
+                    return Err(BackendConfigError::InvertedSslVersionRange);
+                }
+            }
+
             check_for_unrecognized_keys(&toml)?;
 
             Ok(Self {
@@ -192,10 +417,388 @@ mod deserialization {
                 cert_host,
                 use_sni,
                 grpc,
-                // NOTE: Update when we support client certs in static backends
-                client_cert: None,
+                client_cert,
+                connect_timeout,
+                first_byte_timeout,
+                between_bytes_timeout,
+                ca_certificate,
+                ssl_min_version,
+                ssl_max_version,
                 handler: None,
             })
         }
     }
 }
+
+/// This module contains a [`Serialize`][serde::Serialize] implementation for [`Backend`], the
+/// inverse of the [`TryFrom<Table>`][deserialization] impl above.
+///
+/// This is what lets a live [`BackendsConfig`] be written back out to a `fastly.toml`-shaped
+/// TOML document, e.g. for [`backends_watcher`][super::backends_watcher]'s save path.
+mod serialization {
+    use {
+        super::Backend,
+        serde::ser::{Serialize, SerializeMap, Serializer},
+    };
+
+    impl Serialize for Backend {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: Serializer,
+        {
+            // `None` since we don't know the final key count up front (it depends on which
+            // optional fields are set).
+            let mut map = serializer.serialize_map(None)?;
+
+            map.serialize_entry("url", &self.uri.to_string())?;
+            if let Some(override_host) = &self.override_host {
+                map.serialize_entry("override_host", override_host.to_str().unwrap_or_default())?;
+            }
+            if let Some(cert_host) = &self.cert_host {
+                map.serialize_entry("cert_host", cert_host)?;
+            }
+            map.serialize_entry("use_sni", &self.use_sni)?;
+            map.serialize_entry("grpc", &self.grpc)?;
+
+            if let Some(connect_timeout) = self.connect_timeout {
+                map.serialize_entry("connect_timeout", &(connect_timeout.as_millis() as u64))?;
+            }
+            if let Some(first_byte_timeout) = self.first_byte_timeout {
+                map.serialize_entry(
+                    "first_byte_timeout",
+                    &(first_byte_timeout.as_millis() as u64),
+                )?;
+            }
+            if let Some(between_bytes_timeout) = self.between_bytes_timeout {
+                map.serialize_entry(
+                    "between_bytes_timeout",
+                    &(between_bytes_timeout.as_millis() as u64),
+                )?;
+            }
+
+            if let Some(ssl_min_version) = self.ssl_min_version {
+                map.serialize_entry("ssl_min_version", protocol_version_str(ssl_min_version))?;
+            }
+            if let Some(ssl_max_version) = self.ssl_max_version {
+                map.serialize_entry("ssl_max_version", protocol_version_str(ssl_max_version))?;
+            }
+
+            if let Some(ca_certificate) = &self.ca_certificate {
+                map.serialize_entry("ca_certificate", &pem_encode_certs(ca_certificate))?;
+            }
+
+            // `client_cert` and `handler` are runtime-only fields that never came from (and so
+            // never get written back to) `fastly.toml`.
+
+            map.end()
+        }
+    }
+
+    fn protocol_version_str(version: rustls::ProtocolVersion) -> &'static str {
+        match version {
+            rustls::ProtocolVersion::TLSv1_2 => "1.2",
+            rustls::ProtocolVersion::TLSv1_3 => "1.3",
+            _ => "1.3",
+        }
+    }
+
+    /// Re-encode a chain of DER certificates as a single PEM string, the inverse of
+    /// `rustls_pemfile::certs`.
+    fn pem_encode_certs(certs: &[rustls::Certificate]) -> String {
+        certs
+            .iter()
+            .map(|cert| pem_encode_block("CERTIFICATE", &cert.0))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// Encode a single PEM block (RFC 7468), base64-wrapped at 64 columns.
+    fn pem_encode_block(label: &str, der: &[u8]) -> String {
+        let body = base64_encode(der);
+        let mut out = format!("-----BEGIN {label}-----\n");
+        for line in body.as_bytes().chunks(64) {
+            out.push_str(std::str::from_utf8(line).expect("base64 output is ASCII"));
+            out.push('\n');
+        }
+        out.push_str(&format!("-----END {label}-----\n"));
+        out
+    }
+
+    /// A small standard-alphabet base64 encoder, since this is the only place in the crate that
+    /// needs to produce (rather than consume) PEM data.
+    fn base64_encode(data: &[u8]) -> String {
+        const ALPHABET: &[u8; 64] =
+            b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+        let mut out = String::with_capacity((data.len() + 2) / 3 * 4);
+        for chunk in data.chunks(3) {
+            let b0 = chunk[0];
+            let b1 = chunk.get(1).copied();
+            let b2 = chunk.get(2).copied();
+
+            out.push(ALPHABET[(b0 >> 2) as usize] as char);
+            out.push(ALPHABET[(((b0 & 0b11) << 4) | (b1.unwrap_or(0) >> 4)) as usize] as char);
+            out.push(match b1 {
+                Some(b1) => {
+                    ALPHABET[(((b1 & 0b1111) << 2) | (b2.unwrap_or(0) >> 6)) as usize] as char
+                }
+                None => '=',
+            });
+            out.push(match b2 {
+                Some(b2) => ALPHABET[(b2 & 0b0011_1111) as usize] as char,
+                None => '=',
+            });
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::BackendConfigError;
+
+    /// Parse a TOML snippet (the body of a single `[backends.foo]` entry) into a [`Table`].
+    fn table(src: &str) -> toml::value::Table {
+        src.parse::<toml::Value>()
+            .unwrap()
+            .as_table()
+            .unwrap()
+            .clone()
+    }
+
+    fn try_backend(src: &str) -> Result<Backend, BackendConfigError> {
+        Backend::try_from(table(src))
+    }
+
+    #[test]
+    fn rejects_non_integer_connect_timeout() {
+        let err = try_backend(
+            r#"url = "http://example.com"
+               connect_timeout = "soon""#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            BackendConfigError::InvalidTimeoutEntry {
+                key: "connect_timeout"
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_negative_timeout() {
+        let err = try_backend(
+            r#"url = "http://example.com"
+               first_byte_timeout = -1"#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            BackendConfigError::InvalidTimeoutEntry {
+                key: "first_byte_timeout"
+            }
+        ));
+    }
+
+    #[test]
+    fn parses_valid_timeouts() {
+        let backend = try_backend(
+            r#"url = "http://example.com"
+               connect_timeout = 1000
+               first_byte_timeout = 2000
+               between_bytes_timeout = 3000"#,
+        )
+        .unwrap();
+        assert_eq!(backend.connect_timeout, Some(Duration::from_millis(1000)));
+        assert_eq!(
+            backend.first_byte_timeout,
+            Some(Duration::from_millis(2000))
+        );
+        assert_eq!(
+            backend.between_bytes_timeout,
+            Some(Duration::from_millis(3000))
+        );
+    }
+
+    #[test]
+    fn rejects_incomplete_client_cert() {
+        let err = try_backend(
+            r#"url = "http://example.com"
+               client_certificate = "cert only""#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, BackendConfigError::IncompleteClientCert));
+
+        let err = try_backend(
+            r#"url = "http://example.com"
+               client_key = "key only""#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, BackendConfigError::IncompleteClientCert));
+    }
+
+    #[test]
+    fn rejects_invalid_client_cert_pem() {
+        let err = try_backend(
+            r#"url = "http://example.com"
+               client_certificate = "not a cert"
+               client_key = "not a key""#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, BackendConfigError::InvalidClientCert(_)));
+    }
+
+    #[test]
+    fn rejects_invalid_ssl_version_entry() {
+        let err = try_backend(
+            r#"url = "http://example.com"
+               ssl_min_version = "1.1""#,
+        )
+        .unwrap_err();
+        assert!(matches!(
+            err,
+            BackendConfigError::InvalidSslVersionEntry {
+                key: "ssl_min_version"
+            }
+        ));
+    }
+
+    #[test]
+    fn rejects_inverted_ssl_version_range() {
+        let err = try_backend(
+            r#"url = "http://example.com"
+               ssl_min_version = "1.3"
+               ssl_max_version = "1.2""#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, BackendConfigError::InvertedSslVersionRange));
+    }
+
+    #[test]
+    fn accepts_equal_ssl_version_range() {
+        let backend = try_backend(
+            r#"url = "http://example.com"
+               ssl_min_version = "1.2"
+               ssl_max_version = "1.2""#,
+        )
+        .unwrap();
+        assert_eq!(backend.ssl_min_version, Some(ProtocolVersion::TLSv1_2));
+        assert_eq!(backend.ssl_max_version, Some(ProtocolVersion::TLSv1_2));
+    }
+
+    #[test]
+    fn rejects_invalid_ca_certificate_pem() {
+        let err = try_backend(
+            r#"url = "http://example.com"
+               ca_certificate = "not a cert""#,
+        )
+        .unwrap_err();
+        assert!(matches!(err, BackendConfigError::InvalidCaCertificatePem));
+    }
+
+    fn sample_backend() -> Backend {
+        try_backend(r#"url = "http://example.com""#).unwrap()
+    }
+
+    #[test]
+    fn register_dynamic_rejects_name_already_registered_dynamically() {
+        let backends = BackendsConfig::from(HashMap::new());
+        backends
+            .register_dynamic("foo".to_owned(), sample_backend(), None)
+            .unwrap();
+
+        let err = backends
+            .register_dynamic("foo".to_owned(), sample_backend(), None)
+            .unwrap_err();
+        assert!(matches!(err, DynamicBackendError::NameAlreadyInUse(name) if name == "foo"));
+    }
+
+    #[test]
+    fn register_dynamic_rejects_name_already_used_statically() {
+        let mut static_backends = HashMap::new();
+        static_backends.insert("foo".to_owned(), Arc::new(sample_backend()));
+        let backends = BackendsConfig::from(static_backends);
+
+        let err = backends
+            .register_dynamic("foo".to_owned(), sample_backend(), None)
+            .unwrap_err();
+        assert!(matches!(err, DynamicBackendError::NameAlreadyInUse(name) if name == "foo"));
+    }
+
+    #[test]
+    fn get_finds_both_static_and_dynamic_backends() {
+        let mut static_backends = HashMap::new();
+        static_backends.insert("static".to_owned(), Arc::new(sample_backend()));
+        let backends = BackendsConfig::from(static_backends);
+        backends
+            .register_dynamic("dynamic".to_owned(), sample_backend(), None)
+            .unwrap();
+
+        assert!(backends.get("static").is_some());
+        assert!(backends.get("dynamic").is_some());
+        assert!(backends.get("missing").is_none());
+    }
+
+    /// A throwaway self-signed certificate, used only to exercise PEM round-tripping.
+    const TEST_CA_CERTIFICATE: &str = r#"-----BEGIN CERTIFICATE-----
+MIIDFzCCAf+gAwIBAgIUZrZ8HfM4ZkFOKaF9IJGp+wyyALswDQYJKoZIhvcNAQEL
+BQAwGzEZMBcGA1UEAwwQdGVzdC5leGFtcGxlLmNvbTAeFw0yNjA3MjkwNzQ4NDZa
+Fw0zNjA3MjYwNzQ4NDZaMBsxGTAXBgNVBAMMEHRlc3QuZXhhbXBsZS5jb20wggEi
+MA0GCSqGSIb3DQEBAQUAA4IBDwAwggEKAoIBAQDDzz0GXXU58cFfWOpdzcg9voJq
+7+5qFSBRlET7Lwqj+MAGnnPUK6hMR+02uO1Wc5GsTws86wP8w7kjP80UQql2g4BP
+uxdhv+UP/d55qMzl8m9Yl0XUno0syASP8vrPO1rMOfFCtU+14RL8Xm1yFIx3obJB
+pI+l5ALbaJgJe44Ms7uGXOQwjUbpJidkuHopSy/JXEWlnJxsAn17sXj+tadyFGTI
+4iWZGiIfJs2wq41t+52ru3s+PzGfcZtdLQH+0JNIiVWqLWDOcNg+bKIfVth1u6qy
+xxMQ2FFSiXkVGif7FM+AxEXUuc8pZ/P14ABPRITfPV2/GX4Aq205SNgztUQNAgMB
+AAGjUzBRMB0GA1UdDgQWBBTtukUs4OVX0uN77DLSGjvIgRetNzAfBgNVHSMEGDAW
+gBTtukUs4OVX0uN77DLSGjvIgRetNzAPBgNVHRMBAf8EBTADAQH/MA0GCSqGSIb3
+DQEBCwUAA4IBAQCFk/3EKK4TTogncXKD622VxmdmnEGvAGNpW4/L7WO9hX3HkVXF
+RWzlUYiRjluzc+Wx4O+zNrEp6mURZHZDrgD5liPBSWpf0GsyoeoB+ApreZIWbRLB
+fdPeGQX43dDu3Tb++sIIsZLynNVmlIUcyYAeuwlGwUAr08zscDo5Lze6HPjVTvO+
+4atR4n8WHtGtc1J7kXjIsBV+CJC1DWxgIB00LrwxruPgtKks3OQvbs6fbGkOdxCm
+NeOn0UOEbPtsHetdb0ucmiOg3OHH9fJN8pgwRpw1NPGaEuIg0SetzKPPUj1LuEBR
+k4+oZgEFdugMY/6ihwSoMXClROEXg+6w9MQH
+-----END CERTIFICATE-----
+"#;
+
+    /// A round trip through `Serialize` and back through `TryFrom<Table>` should reproduce every
+    /// field that can appear in a `fastly.toml`, including `ca_certificate` — this is the test
+    /// that would have caught it being silently dropped on serialize.
+    #[test]
+    fn backend_round_trips_through_toml() {
+        let original = try_backend(&format!(
+            r#"url = "https://example.com"
+               cert_host = "example.com"
+               use_sni = false
+               grpc = true
+               connect_timeout = 1000
+               first_byte_timeout = 2000
+               between_bytes_timeout = 3000
+               ssl_min_version = "1.2"
+               ssl_max_version = "1.3"
+               ca_certificate = {TEST_CA_CERTIFICATE:?}"#
+        ))
+        .unwrap();
+
+        let value = toml::Value::try_from(&original).unwrap();
+        let round_tripped = Backend::try_from(value.as_table().unwrap().clone()).unwrap();
+
+        assert_eq!(original.uri, round_tripped.uri);
+        assert_eq!(original.cert_host, round_tripped.cert_host);
+        assert_eq!(original.use_sni, round_tripped.use_sni);
+        assert_eq!(original.grpc, round_tripped.grpc);
+        assert_eq!(original.connect_timeout, round_tripped.connect_timeout);
+        assert_eq!(
+            original.first_byte_timeout,
+            round_tripped.first_byte_timeout
+        );
+        assert_eq!(
+            original.between_bytes_timeout,
+            round_tripped.between_bytes_timeout
+        );
+        assert_eq!(original.ssl_min_version, round_tripped.ssl_min_version);
+        assert_eq!(original.ssl_max_version, round_tripped.ssl_max_version);
+        assert_eq!(original.ca_certificate, round_tripped.ca_certificate);
+    }
+}
@@ -0,0 +1,236 @@
+//! Hot-reloading for the `[backends]` table of a `fastly.toml`, so long-lived `viceroy` sessions
+//! don't need to be restarted to pick up backend edits.
+
+use {
+    super::{Backend, BackendsConfig},
+    crate::error::{BackendConfigError, FastlyConfigError},
+    std::{
+        collections::HashMap,
+        path::{Path, PathBuf},
+        sync::Arc,
+        time::{Duration, SystemTime},
+    },
+    tokio::{fs, sync::RwLock, task::JoinHandle},
+};
+
+/// Watches a `fastly.toml`'s `[backends]` table for changes and atomically swaps the live
+/// [`BackendsConfig`] when the file is edited, without tearing down backends that are still
+/// valid if some other backend's edit was bad.
+pub struct BackendsWatcher {
+    path: PathBuf,
+    live: Arc<RwLock<BackendsConfig>>,
+}
+
+impl BackendsWatcher {
+    /// Create a new watcher over `path`'s `[backends]` table, seeded with the backends already
+    /// loaded at startup.
+    pub fn new(path: impl Into<PathBuf>, initial: BackendsConfig) -> Self {
+        Self {
+            path: path.into(),
+            live: Arc::new(RwLock::new(initial)),
+        }
+    }
+
+    /// A handle to the live backends map, kept up to date by the background reload task.
+    pub fn backends(&self) -> Arc<RwLock<BackendsConfig>> {
+        self.live.clone()
+    }
+
+    /// Persist the current static backends back to `path`, via a write-then-rename so a reader
+    /// (including our own poll loop) never observes a partially written file.
+    pub async fn save(&self) -> std::io::Result<()> {
+        let table = self.live.read().await.to_toml_table();
+        save_backends_table(&self.path, table).await
+    }
+
+    /// Spawn a background task that polls `path` for changes every `poll_interval` and reloads
+    /// the `[backends]` table when its modification time changes.
+    ///
+    /// Takes `&self` rather than consuming the watcher, so a caller can still hold onto it (and
+    /// call `save`, or read `backends` directly) after spawning the reload task.
+    pub fn spawn(&self, poll_interval: Duration) -> JoinHandle<()> {
+        let path = self.path.clone();
+        let live = self.live.clone();
+        tokio::spawn(async move {
+            let mut last_modified = modified_time(&path).await;
+            loop {
+                tokio::time::sleep(poll_interval).await;
+
+                let modified = modified_time(&path).await;
+                if modified.is_none() || modified == last_modified {
+                    continue;
+                }
+                last_modified = modified;
+
+                match load_backends_table(&path).await {
+                    Ok((static_backends, errors)) => {
+                        for err in &errors {
+                            tracing::warn!("error reloading backend from {:?}: {err}", path);
+                        }
+                        // Update only the static half in place: replacing the whole
+                        // `BackendsConfig` would reset `dynamic_backends` and drop every backend
+                        // registered at request time via `register_dynamic`.
+                        live.write().await.replace_static(static_backends);
+                    }
+                    Err(err) => {
+                        tracing::warn!("failed to reload {:?}: {err}", path);
+                    }
+                }
+            }
+        })
+    }
+}
+
+async fn modified_time(path: &Path) -> Option<SystemTime> {
+    fs::metadata(path).await.ok()?.modified().ok()
+}
+
+/// Read and parse the `[backends]` table out of a `fastly.toml`, returning the backends that
+/// parsed successfully alongside a [`FastlyConfigError`] for each one that didn't. A single bad
+/// backend definition doesn't prevent the rest of the table from reloading.
+async fn load_backends_table(
+    path: &Path,
+) -> std::io::Result<(HashMap<String, Arc<Backend>>, Vec<FastlyConfigError>)> {
+    let contents = fs::read_to_string(path).await?;
+    let doc: toml::Value = contents
+        .parse()
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    let backends_table = doc
+        .get("backends")
+        .and_then(toml::Value::as_table)
+        .cloned()
+        .unwrap_or_default();
+
+    let mut static_backends = HashMap::new();
+    let mut errors = Vec::new();
+    for (name, defs) in backends_table {
+        let result = match defs {
+            toml::Value::Table(table) => Backend::try_from(table),
+            _ => Err(BackendConfigError::InvalidEntryType),
+        };
+        match result {
+            Ok(backend) => {
+                static_backends.insert(name, Arc::new(backend));
+            }
+            Err(err) => errors.push(FastlyConfigError::InvalidBackendDefinition { name, err }),
+        }
+    }
+
+    Ok((static_backends, errors))
+}
+
+/// Write `table` back into `path`'s `[backends]` key, preserving the rest of the document, via a
+/// temp file and rename so a crash or concurrent read never sees a half-written file.
+async fn save_backends_table(path: &Path, table: toml::value::Table) -> std::io::Result<()> {
+    let mut doc: toml::Value = match fs::read_to_string(path).await {
+        Ok(contents) => contents
+            .parse()
+            .unwrap_or_else(|_| toml::Value::Table(Default::default())),
+        Err(_) => toml::Value::Table(Default::default()),
+    };
+
+    if let toml::Value::Table(doc) = &mut doc {
+        doc.insert("backends".to_owned(), toml::Value::Table(table));
+    }
+
+    let contents = toml::to_string_pretty(&doc)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+    let tmp_path = path.with_extension("toml.tmp");
+    fs::write(&tmp_path, contents).await?;
+    fs::rename(&tmp_path, path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    fn temp_toml_path() -> PathBuf {
+        static COUNTER: AtomicU64 = AtomicU64::new(0);
+        let n = COUNTER.fetch_add(1, Ordering::Relaxed);
+        std::env::temp_dir().join(format!(
+            "viceroy-backends-watcher-test-{}-{n}.toml",
+            std::process::id()
+        ))
+    }
+
+    fn sample_backend() -> Backend {
+        Backend::try_from(
+            r#"url = "http://example.com""#
+                .parse::<toml::Value>()
+                .unwrap()
+                .as_table()
+                .unwrap()
+                .clone(),
+        )
+        .unwrap()
+    }
+
+    /// Reloading after an on-disk edit should pick up new/changed static backends, tolerate a
+    /// bad entry alongside good ones, and leave dynamically registered backends alone.
+    #[tokio::test]
+    async fn reload_updates_static_backends_without_dropping_dynamic_ones() {
+        let path = temp_toml_path();
+        fs::write(
+            &path,
+            r#"[backends.one]
+url = "http://one.example.com"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let (static_backends, errors) = load_backends_table(&path).await.unwrap();
+        assert!(errors.is_empty());
+        let watcher = BackendsWatcher::new(&path, BackendsConfig::from(static_backends));
+
+        watcher
+            .backends()
+            .read()
+            .await
+            .register_dynamic("dyn".to_owned(), sample_backend(), None)
+            .unwrap();
+
+        let handle = watcher.spawn(Duration::from_millis(20));
+
+        // `bad` is missing a `url` and so can't parse; `one` and `two` should still reload.
+        fs::write(
+            &path,
+            r#"[backends.one]
+url = "http://one.example.com"
+
+[backends.two]
+url = "http://two.example.com"
+
+[backends.bad]
+connect_timeout = "not a number"
+"#,
+        )
+        .await
+        .unwrap();
+
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(5);
+        while watcher.backends().read().await.get("two").is_none() {
+            assert!(
+                tokio::time::Instant::now() < deadline,
+                "reload did not pick up the new backend in time"
+            );
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+
+        let live = watcher.backends();
+        let live = live.read().await;
+        assert!(live.get("one").is_some());
+        assert!(live.get("two").is_some());
+        assert!(
+            live.get("dyn").is_some(),
+            "dynamic backend should survive a static reload"
+        );
+        drop(live);
+
+        handle.abort();
+        let _ = fs::remove_file(&path).await;
+    }
+}
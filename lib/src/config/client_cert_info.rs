@@ -0,0 +1,72 @@
+use rustls::{Certificate, PrivateKey};
+
+/// A client certificate chain and private key used for mutual TLS against a backend.
+#[derive(Clone)]
+pub struct ClientCertInfo {
+    pub certificates: Vec<Certificate>,
+    pub key: PrivateKey,
+}
+
+impl ClientCertInfo {
+    pub fn new(certificates: Vec<Certificate>, key: PrivateKey) -> Self {
+        Self { certificates, key }
+    }
+
+    /// Parse a PEM-encoded certificate chain and private key into a [`ClientCertInfo`].
+    ///
+    /// The private key may be PKCS#8 (`BEGIN PRIVATE KEY`), PKCS#1 (`BEGIN RSA PRIVATE KEY`), or
+    /// SEC1 (`BEGIN EC PRIVATE KEY`) — all three are common for client certs in the wild.
+    pub fn parse(cert_pem: &[u8], key_pem: &[u8]) -> Result<Self, ClientCertError> {
+        let certificates = rustls_pemfile::certs(&mut &cert_pem[..])
+            .map_err(ClientCertError::InvalidPem)?
+            .into_iter()
+            .map(Certificate)
+            .collect::<Vec<_>>();
+        if certificates.is_empty() {
+            return Err(ClientCertError::NoCertificate);
+        }
+
+        let key = read_private_key(key_pem)?.ok_or(ClientCertError::NoPrivateKey)?;
+
+        Ok(Self::new(certificates, PrivateKey(key)))
+    }
+}
+
+/// Read the first private key out of `pem`, accepting PKCS#8, PKCS#1, or SEC1 encoding.
+fn read_private_key(pem: &[u8]) -> Result<Option<Vec<u8>>, ClientCertError> {
+    let mut reader = &mut &pem[..];
+    loop {
+        match rustls_pemfile::read_one(&mut reader).map_err(ClientCertError::InvalidPem)? {
+            Some(
+                rustls_pemfile::Item::PKCS8Key(key)
+                | rustls_pemfile::Item::RSAKey(key)
+                | rustls_pemfile::Item::ECKey(key),
+            ) => return Ok(Some(key)),
+            Some(_) => continue,
+            None => return Ok(None),
+        }
+    }
+}
+
+impl std::fmt::Debug for ClientCertInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClientCertInfo")
+            .field(
+                "certificates",
+                &format!("{} certificate(s)", self.certificates.len()),
+            )
+            .field("key", &"<private key>")
+            .finish()
+    }
+}
+
+/// Errors that can occur while parsing a client certificate chain or private key from PEM.
+#[derive(Debug, thiserror::Error)]
+pub enum ClientCertError {
+    #[error("no certificate was found in the given PEM data")]
+    NoCertificate,
+    #[error("no private key was found in the given PEM data")]
+    NoPrivateKey,
+    #[error("the given PEM data could not be parsed: {0}")]
+    InvalidPem(std::io::Error),
+}
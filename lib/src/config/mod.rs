@@ -0,0 +1,5 @@
+pub mod backends;
+pub mod backends_watcher;
+
+pub use backends::{Backend, BackendsConfig, DynamicBackendRegistrar, Handler};
+pub use backends_watcher::BackendsWatcher;
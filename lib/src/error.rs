@@ -0,0 +1,95 @@
+//! Errors surfaced while parsing and validating `fastly.toml`.
+
+/// Errors that can occur while parsing a single backend definition.
+#[derive(Debug, thiserror::Error)]
+pub enum BackendConfigError {
+    #[error("Found a TOML entry that was not a table")]
+    InvalidEntryType,
+    #[error("Found an unrecognized key: {0}")]
+    UnrecognizedKey(String),
+    #[error("No `url` entry was found in the backend definition")]
+    MissingUrl,
+    #[error("The `url` entry was not a string")]
+    InvalidUrlEntry,
+    #[error("The backend URI was invalid: {0}")]
+    InvalidUri(#[from] http::uri::InvalidUri),
+    #[error("The `override_host` entry was empty")]
+    EmptyOverrideHost,
+    #[error("The `override_host` entry was not a string")]
+    InvalidOverrideHostEntry,
+    #[error("The `cert_host` entry was empty")]
+    EmptyCertHost,
+    #[error("The `cert_host` entry was not a string")]
+    InvalidCertHostEntry,
+    #[error("The `use_sni` entry was not a boolean")]
+    InvalidUseSniEntry,
+    #[error("The `grpc` entry was not a boolean")]
+    InvalidGrpcEntry,
+    #[error("The header value was invalid: {0}")]
+    InvalidHeaderValue(#[from] http::header::InvalidHeaderValue),
+    #[error("The `{key}` entry was not an integer number of milliseconds")]
+    InvalidTimeoutEntry { key: &'static str },
+    #[error("Both `client_certificate` and `client_key` must be given together, or not at all")]
+    IncompleteClientCert,
+    #[error("The `client_certificate` entry was not a string")]
+    InvalidClientCertificateEntry,
+    #[error("The `client_key` entry was not a string")]
+    InvalidClientKeyEntry,
+    #[error("Could not read client certificate data from file: {0}")]
+    InvalidClientCertPath(std::io::Error),
+    #[error("Invalid client certificate: {0}")]
+    InvalidClientCert(#[from] crate::config::backends::ClientCertError),
+    #[error("The `ca_certificate` entry was not a string")]
+    InvalidCaCertificateEntry,
+    #[error("The `ca_certificate` entry did not contain a valid PEM certificate")]
+    InvalidCaCertificatePem,
+    #[error("The `{key}` entry was not one of \"1.2\" or \"1.3\"")]
+    InvalidSslVersionEntry { key: &'static str },
+    #[error("`ssl_min_version` cannot be greater than `ssl_max_version`")]
+    InvertedSslVersionRange,
+}
+
+/// Errors that can occur while parsing the `[backends]` section, or any other
+/// top-level section, of a `fastly.toml`.
+#[derive(Debug, thiserror::Error)]
+pub enum FastlyConfigError {
+    #[error("invalid definition for backend '{name}': {err}")]
+    InvalidBackendDefinition {
+        name: String,
+        err: BackendConfigError,
+    },
+}
+
+/// Errors that can occur while registering a backend at request time via
+/// `register_dynamic_backend`.
+#[derive(Debug, thiserror::Error)]
+pub enum DynamicBackendError {
+    #[error("a backend named '{0}' already exists")]
+    NameAlreadyInUse(String),
+}
+
+/// Errors that can occur while sending a request to a backend.
+///
+/// These map onto the same "backend unavailable"-style outcomes the WASI hostcalls report to
+/// the guest on a stalled or unreachable backend.
+#[derive(Debug, thiserror::Error)]
+pub enum UpstreamError {
+    #[error("backend URI has no authority")]
+    InvalidBackendUri,
+    #[error("timed out connecting to backend")]
+    ConnectTimeout,
+    #[error("timed out waiting for the first byte of the response")]
+    FirstByteTimeout,
+    #[error("timed out waiting for the next chunk of the response body")]
+    BetweenBytesTimeout,
+    #[error("I/O error talking to backend: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("HTTP error talking to backend: {0}")]
+    Hyper(#[from] hyper::Error),
+    #[error("invalid CA certificate configured for this backend")]
+    InvalidCaCertificate,
+    #[error("invalid client certificate configured for this backend")]
+    InvalidClientCert,
+    #[error("`ssl_min_version` cannot be greater than `ssl_max_version`")]
+    InvertedSslVersionRange,
+}